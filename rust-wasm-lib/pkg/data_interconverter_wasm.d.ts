@@ -3,16 +3,59 @@
 export function set_panic_hook(): void;
 export class AppState {
   free(): void;
-  constructor();
+  exportWav(sample_rate: number, channels: number, bits_per_sample: number): Uint8Array;
+  exportImage(width: number, height: number, pixel_format: string, container: string): Uint8Array;
+  getGrayCode(): string;
+  indexToText(alphabet_id: string): string;
+  getCombination(n: number, k: number): Uint32Array;
+  getPermutation(n: number): Uint32Array;
+  getImagePixels(width: number, height: number, pixel_format: string): Uint32Array;
+  setIndexFromCsv(csv: string, schema_json: string): void;
+  setIndexFromWav(wav_bytes: Uint8Array): void;
+  getAnalysisReport(): any;
   getCanonicalIndex(): bigint;
   setCanonicalIndex(js_idx: bigint): void;
-  getSequenceRepresentation(tl: number, bd: number): any;
-  calculateMinSequenceLength(bd: number): number;
+  setIndexFromJson(json_s: string): void;
+  setIndexFromText(txt: string, alphabet_id: string): void;
   indexToTextSimple(): string;
+  setIndexFromBytes(bytes: Uint8Array, length_preserving: boolean): void;
+  setIndexFromImage(image_bytes: Uint8Array): any;
+  getTextAlphabetIds(): string[];
+  indexToUnicodeText(): string;
+  getBytesFixedLength(length: number): Uint8Array;
+  getCanonicalIndexAs(encoding: string): string;
+  registerTextAlphabet(alphabet_id: string, symbols: string, case_insensitive: boolean, bijective: boolean): void;
+  setIndexFromSequence(values: any, bd: number): void;
+  setCanonicalIndexFrom(encoding: string, encoded: string): void;
+  setIndexFromGrayCode(bits: string): void;
+  setIndexFromZeckendorf(digits: string): void;
+  static getAnalysisReportSchema(): string;
+  setIndexFromCombination(elements: Uint32Array): void;
+  setIndexFromMixedRadix(digits: Uint32Array, radices: Uint32Array): void;
+  setIndexFromPermutation(permutation: Uint32Array): void;
   setIndexFromTextSimple(txt: string): void;
+  getSequenceRepresentation(tl: number, bd: number): any;
+  setIndexFromImagePixels(pixels: Uint32Array, pixel_format: string): void;
+  setIndexFromUnicodeText(txt: string): void;
+  calculateMinSequenceLength(bd: number): number;
+  getZeckendorfRepresentation(): string;
+  runInternalValidationSuite(): string;
+  getMixedRadixRepresentation(radices: Uint32Array): Uint32Array;
+  setIndexFromSignedSequence(values: Int32Array, bd: number, encoding: string): void;
   executeJsonInstructionsToCI(json_s: string): bigint;
+  getSequenceRepresentationU64(tl: number, bd: number): BigUint64Array;
+  setIndexFromSequenceInBase(values: any, base: number): void;
+  getSignedSequenceRepresentation(tl: number, bd: number, encoding: string): Int32Array;
+  getSequenceRepresentationDecimal(tl: number, bd: number): string[];
+  getSequenceRepresentationInBase(tl: number, base: number): Uint32Array;
+  setIndexFromSequenceWithLayout(values: Uint32Array, bd: number, word_order: string, bit_order: string): void;
+  calculateMinSequenceLengthInBase(base: number): number;
+  getSequenceRepresentationWithLayout(tl: number, bd: number, word_order: string, bit_order: string): Uint32Array;
+  constructor();
   generateJsonAnalysisReportForCurrentCI(_strategy: string): string;
-  runInternalValidationSuite(): string;
+  getCsv(schema_json: string): string;
+  getJson(): string;
+  getBytes(length_preserving: boolean): Uint8Array;
 }
 
 export type InitInput = RequestInfo | URL | Response | BufferSource | WebAssembly.Module;
@@ -20,16 +63,59 @@ export type InitInput = RequestInfo | URL | Response | BufferSource | WebAssembl
 export interface InitOutput {
   readonly memory: WebAssembly.Memory;
   readonly __wbg_appstate_free: (a: number, b: number) => void;
-  readonly appstate_new: () => number;
-  readonly appstate_getCanonicalIndex: (a: number) => [number, number, number];
-  readonly appstate_setCanonicalIndex: (a: number, b: any) => [number, number];
-  readonly appstate_getSequenceRepresentation: (a: number, b: number, c: number) => [number, number, number];
   readonly appstate_calculateMinSequenceLength: (a: number, b: number) => [number, number, number];
-  readonly appstate_indexToTextSimple: (a: number) => [number, number, number, number];
-  readonly appstate_setIndexFromTextSimple: (a: number, b: number, c: number) => [number, number];
+  readonly appstate_calculateMinSequenceLengthInBase: (a: number, b: number) => [number, number, number];
   readonly appstate_executeJsonInstructionsToCI: (a: number, b: number, c: number) => [number, number, number];
+  readonly appstate_exportImage: (a: number, b: number, c: number, d: number, e: number, f: number, g: number) => [number, number, number, number];
+  readonly appstate_exportWav: (a: number, b: number, c: number, d: number) => [number, number, number, number];
   readonly appstate_generateJsonAnalysisReportForCurrentCI: (a: number, b: number, c: number) => [number, number, number, number];
+  readonly appstate_getAnalysisReport: (a: number) => [number, number, number];
+  readonly appstate_getAnalysisReportSchema: () => [number, number];
+  readonly appstate_getBytes: (a: number, b: number) => [number, number, number, number];
+  readonly appstate_getBytesFixedLength: (a: number, b: number) => [number, number, number, number];
+  readonly appstate_getCanonicalIndex: (a: number) => [number, number, number];
+  readonly appstate_getCanonicalIndexAs: (a: number, b: number, c: number) => [number, number, number, number];
+  readonly appstate_getCombination: (a: number, b: number, c: number) => [number, number, number, number];
+  readonly appstate_getCsv: (a: number, b: number, c: number) => [number, number, number, number];
+  readonly appstate_getGrayCode: (a: number) => [number, number, number, number];
+  readonly appstate_getImagePixels: (a: number, b: number, c: number, d: number, e: number) => [number, number, number, number];
+  readonly appstate_getJson: (a: number) => [number, number, number, number];
+  readonly appstate_getMixedRadixRepresentation: (a: number, b: number, c: number) => [number, number, number, number];
+  readonly appstate_getPermutation: (a: number, b: number) => [number, number, number, number];
+  readonly appstate_getSequenceRepresentation: (a: number, b: number, c: number) => [number, number, number];
+  readonly appstate_getSequenceRepresentationDecimal: (a: number, b: number, c: number) => [number, number, number, number];
+  readonly appstate_getSequenceRepresentationInBase: (a: number, b: number, c: number) => [number, number, number, number];
+  readonly appstate_getSequenceRepresentationU64: (a: number, b: number, c: number) => [number, number, number, number];
+  readonly appstate_getSequenceRepresentationWithLayout: (a: number, b: number, c: number, d: number, e: number, f: number, g: number) => [number, number, number, number];
+  readonly appstate_getSignedSequenceRepresentation: (a: number, b: number, c: number, d: number, e: number) => [number, number, number, number];
+  readonly appstate_getTextAlphabetIds: (a: number) => [number, number];
+  readonly appstate_getZeckendorfRepresentation: (a: number) => [number, number, number, number];
+  readonly appstate_indexToText: (a: number, b: number, c: number) => [number, number, number, number];
+  readonly appstate_indexToTextSimple: (a: number) => [number, number, number, number];
+  readonly appstate_indexToUnicodeText: (a: number) => [number, number, number, number];
+  readonly appstate_new: () => number;
+  readonly appstate_registerTextAlphabet: (a: number, b: number, c: number, d: number, e: number, f: number, g: number) => [number, number];
   readonly appstate_runInternalValidationSuite: (a: number) => [number, number];
+  readonly appstate_setCanonicalIndex: (a: number, b: any) => [number, number];
+  readonly appstate_setCanonicalIndexFrom: (a: number, b: number, c: number, d: number, e: number) => [number, number];
+  readonly appstate_setIndexFromBytes: (a: number, b: number, c: number, d: number) => [number, number];
+  readonly appstate_setIndexFromCombination: (a: number, b: number, c: number) => [number, number];
+  readonly appstate_setIndexFromCsv: (a: number, b: number, c: number, d: number, e: number) => [number, number];
+  readonly appstate_setIndexFromGrayCode: (a: number, b: number, c: number) => [number, number];
+  readonly appstate_setIndexFromImage: (a: number, b: number, c: number) => [number, number, number];
+  readonly appstate_setIndexFromImagePixels: (a: number, b: number, c: number, d: number, e: number) => [number, number];
+  readonly appstate_setIndexFromJson: (a: number, b: number, c: number) => [number, number];
+  readonly appstate_setIndexFromMixedRadix: (a: number, b: number, c: number, d: number, e: number) => [number, number];
+  readonly appstate_setIndexFromPermutation: (a: number, b: number, c: number) => [number, number];
+  readonly appstate_setIndexFromSequence: (a: number, b: any, c: number) => [number, number];
+  readonly appstate_setIndexFromSequenceInBase: (a: number, b: any, c: number) => [number, number];
+  readonly appstate_setIndexFromSequenceWithLayout: (a: number, b: number, c: number, d: number, e: number, f: number, g: number, h: number) => [number, number];
+  readonly appstate_setIndexFromSignedSequence: (a: number, b: number, c: number, d: number, e: number, f: number) => [number, number];
+  readonly appstate_setIndexFromText: (a: number, b: number, c: number, d: number, e: number) => [number, number];
+  readonly appstate_setIndexFromTextSimple: (a: number, b: number, c: number) => [number, number];
+  readonly appstate_setIndexFromUnicodeText: (a: number, b: number, c: number) => [number, number];
+  readonly appstate_setIndexFromWav: (a: number, b: number, c: number) => [number, number];
+  readonly appstate_setIndexFromZeckendorf: (a: number, b: number, c: number) => [number, number];
   readonly set_panic_hook: () => void;
   readonly __wbindgen_exn_store: (a: number) => void;
   readonly __externref_table_alloc: () => number;
@@ -38,6 +124,7 @@ export interface InitOutput {
   readonly __wbindgen_realloc: (a: number, b: number, c: number, d: number) => number;
   readonly __wbindgen_free: (a: number, b: number, c: number) => void;
   readonly __externref_table_dealloc: (a: number) => void;
+  readonly __externref_drop_slice: (a: number, b: number) => void;
   readonly __wbindgen_start: () => void;
 }
 
@@ -2,16 +2,59 @@
 /* eslint-disable */
 export const memory: WebAssembly.Memory;
 export const __wbg_appstate_free: (a: number, b: number) => void;
-export const appstate_new: () => number;
-export const appstate_getCanonicalIndex: (a: number) => [number, number, number];
-export const appstate_setCanonicalIndex: (a: number, b: any) => [number, number];
-export const appstate_getSequenceRepresentation: (a: number, b: number, c: number) => [number, number, number];
 export const appstate_calculateMinSequenceLength: (a: number, b: number) => [number, number, number];
-export const appstate_indexToTextSimple: (a: number) => [number, number, number, number];
-export const appstate_setIndexFromTextSimple: (a: number, b: number, c: number) => [number, number];
+export const appstate_calculateMinSequenceLengthInBase: (a: number, b: number) => [number, number, number];
 export const appstate_executeJsonInstructionsToCI: (a: number, b: number, c: number) => [number, number, number];
+export const appstate_exportImage: (a: number, b: number, c: number, d: number, e: number, f: number, g: number) => [number, number, number, number];
+export const appstate_exportWav: (a: number, b: number, c: number, d: number) => [number, number, number, number];
 export const appstate_generateJsonAnalysisReportForCurrentCI: (a: number, b: number, c: number) => [number, number, number, number];
+export const appstate_getAnalysisReport: (a: number) => [number, number, number];
+export const appstate_getAnalysisReportSchema: () => [number, number];
+export const appstate_getBytes: (a: number, b: number) => [number, number, number, number];
+export const appstate_getBytesFixedLength: (a: number, b: number) => [number, number, number, number];
+export const appstate_getCanonicalIndex: (a: number) => [number, number, number];
+export const appstate_getCanonicalIndexAs: (a: number, b: number, c: number) => [number, number, number, number];
+export const appstate_getCombination: (a: number, b: number, c: number) => [number, number, number, number];
+export const appstate_getCsv: (a: number, b: number, c: number) => [number, number, number, number];
+export const appstate_getGrayCode: (a: number) => [number, number, number, number];
+export const appstate_getImagePixels: (a: number, b: number, c: number, d: number, e: number) => [number, number, number, number];
+export const appstate_getJson: (a: number) => [number, number, number, number];
+export const appstate_getMixedRadixRepresentation: (a: number, b: number, c: number) => [number, number, number, number];
+export const appstate_getPermutation: (a: number, b: number) => [number, number, number, number];
+export const appstate_getSequenceRepresentation: (a: number, b: number, c: number) => [number, number, number];
+export const appstate_getSequenceRepresentationDecimal: (a: number, b: number, c: number) => [number, number, number, number];
+export const appstate_getSequenceRepresentationInBase: (a: number, b: number, c: number) => [number, number, number, number];
+export const appstate_getSequenceRepresentationU64: (a: number, b: number, c: number) => [number, number, number, number];
+export const appstate_getSequenceRepresentationWithLayout: (a: number, b: number, c: number, d: number, e: number, f: number, g: number) => [number, number, number, number];
+export const appstate_getSignedSequenceRepresentation: (a: number, b: number, c: number, d: number, e: number) => [number, number, number, number];
+export const appstate_getTextAlphabetIds: (a: number) => [number, number];
+export const appstate_getZeckendorfRepresentation: (a: number) => [number, number, number, number];
+export const appstate_indexToText: (a: number, b: number, c: number) => [number, number, number, number];
+export const appstate_indexToTextSimple: (a: number) => [number, number, number, number];
+export const appstate_indexToUnicodeText: (a: number) => [number, number, number, number];
+export const appstate_new: () => number;
+export const appstate_registerTextAlphabet: (a: number, b: number, c: number, d: number, e: number, f: number, g: number) => [number, number];
 export const appstate_runInternalValidationSuite: (a: number) => [number, number];
+export const appstate_setCanonicalIndex: (a: number, b: any) => [number, number];
+export const appstate_setCanonicalIndexFrom: (a: number, b: number, c: number, d: number, e: number) => [number, number];
+export const appstate_setIndexFromBytes: (a: number, b: number, c: number, d: number) => [number, number];
+export const appstate_setIndexFromCombination: (a: number, b: number, c: number) => [number, number];
+export const appstate_setIndexFromCsv: (a: number, b: number, c: number, d: number, e: number) => [number, number];
+export const appstate_setIndexFromGrayCode: (a: number, b: number, c: number) => [number, number];
+export const appstate_setIndexFromImage: (a: number, b: number, c: number) => [number, number, number];
+export const appstate_setIndexFromImagePixels: (a: number, b: number, c: number, d: number, e: number) => [number, number];
+export const appstate_setIndexFromJson: (a: number, b: number, c: number) => [number, number];
+export const appstate_setIndexFromMixedRadix: (a: number, b: number, c: number, d: number, e: number) => [number, number];
+export const appstate_setIndexFromPermutation: (a: number, b: number, c: number) => [number, number];
+export const appstate_setIndexFromSequence: (a: number, b: any, c: number) => [number, number];
+export const appstate_setIndexFromSequenceInBase: (a: number, b: any, c: number) => [number, number];
+export const appstate_setIndexFromSequenceWithLayout: (a: number, b: number, c: number, d: number, e: number, f: number, g: number, h: number) => [number, number];
+export const appstate_setIndexFromSignedSequence: (a: number, b: number, c: number, d: number, e: number, f: number) => [number, number];
+export const appstate_setIndexFromText: (a: number, b: number, c: number, d: number, e: number) => [number, number];
+export const appstate_setIndexFromTextSimple: (a: number, b: number, c: number) => [number, number];
+export const appstate_setIndexFromUnicodeText: (a: number, b: number, c: number) => [number, number];
+export const appstate_setIndexFromWav: (a: number, b: number, c: number) => [number, number];
+export const appstate_setIndexFromZeckendorf: (a: number, b: number, c: number) => [number, number];
 export const set_panic_hook: () => void;
 export const __wbindgen_exn_store: (a: number) => void;
 export const __externref_table_alloc: () => number;
@@ -20,4 +63,5 @@ export const __wbindgen_malloc: (a: number, b: number) => number;
 export const __wbindgen_realloc: (a: number, b: number, c: number, d: number) => number;
 export const __wbindgen_free: (a: number, b: number, c: number) => void;
 export const __externref_table_dealloc: (a: number) => void;
+export const __externref_drop_slice: (a: number, b: number) => void;
 export const __wbindgen_start: () => void;
@@ -0,0 +1,74 @@
+// Command-line front end over the native `Converter`. CIs are read and written in decimal; a missing CI/text
+// argument (or `-`) is read from stdin so subcommands chain in pipelines, e.g. `from-text HELLO | to-seq --bits 8 --len 4`.
+
+use data_interconverter_wasm::{ConvertError, Converter};
+use num_bigint::BigUint;
+use std::io::{Read, Write};
+use std::process::ExitCode;
+use std::str::FromStr;
+
+const USAGE: &str = "usage: data-interconverter <command> [args]
+
+commands:
+  exec <instr.json|->                      execute a JSON instruction, print the resulting CI
+  analyze [ci]                             print the JSON analysis report for a CI
+  to-text [--alphabet ID] [ci]             print the text view of a CI
+  from-text [--alphabet ID] [text]         print the CI of a text
+  to-seq --bits N --len L [ci]             print the N-bit sequence view of a CI, one value per line
+  validate                                 run the internal validation suite
+  help                                     show this message
+
+CI and text arguments default to stdin; the default alphabet is SIMPLE_TEXT_A_Z_SPACE.";
+
+enum CliError { Usage(String), Convert(ConvertError), Io(std::io::Error), ValidationFailed(String) }
+
+impl From<ConvertError> for CliError { fn from(e:ConvertError)->Self{CliError::Convert(e)} }
+impl From<std::io::Error> for CliError { fn from(e:std::io::Error)->Self{CliError::Io(e)} }
+
+type CliResult<T> = Result<T, CliError>;
+
+// Splits `--flag value` pairs from positional arguments; every flag takes exactly one value.
+struct Args { flags: Vec<(String, String)>, positional: Vec<String> }
+
+impl Args {
+    fn parse(raw:&[String],known_flags:&[&str])->CliResult<Self>{let mut flags=Vec::new();let mut positional=Vec::new();let mut it=raw.iter();while let Some(a)=it.next(){if let Some(name)=a.strip_prefix("--"){if !known_flags.contains(&name){return Err(CliError::Usage(format!("unknown option '{}'",a)));}let v=it.next().ok_or_else(||CliError::Usage(format!("option '{}' needs a value",a)))?;flags.push((name.to_string(),v.clone()));}else{positional.push(a.clone());}}Ok(Args{flags,positional})}
+    fn flag(&self,name:&str)->Option<&str>{self.flags.iter().rev().find(|(n,_)|n==name).map(|(_,v)|v.as_str())}
+    fn flag_u32(&self,name:&str)->CliResult<u32>{let v=self.flag(name).ok_or_else(||CliError::Usage(format!("missing required option '--{}'",name)))?;v.parse().map_err(|_|CliError::Usage(format!("option '--{}' expects a non-negative integer, got '{}'",name,v)))}
+    // At most one positional argument; absent or `-` means stdin.
+    fn input(&self)->CliResult<String>{match self.positional.as_slice(){[]=>read_stdin(),[p] if p=="-"=>read_stdin(),[p]=>Ok(p.clone()),_=>Err(CliError::Usage(format!("unexpected argument '{}'",self.positional[1])))}}
+}
+
+fn read_stdin()->CliResult<String>{let mut s=String::new();std::io::stdin().read_to_string(&mut s)?;if s.ends_with('\n'){s.pop();if s.ends_with('\r'){s.pop();}}Ok(s)}
+
+fn parse_ci(s:&str)->CliResult<BigUint>{BigUint::from_str(s.trim()).map_err(|_|CliError::Usage(format!("CI must be a non-negative decimal integer, got '{}'",s.trim())))}
+
+fn converter_at(ci_s:&str)->CliResult<Converter>{let mut c=Converter::new();c.set_index(parse_ci(ci_s)?);Ok(c)}
+
+fn run(cmd:&str,rest:&[String])->CliResult<String>{
+    match cmd {
+        "exec"=>{let a=Args::parse(rest,&[])?;let path=a.positional.first().ok_or_else(||CliError::Usage("exec needs an instruction file (or '-')".to_string()))?;if a.positional.len()>1{return Err(CliError::Usage(format!("unexpected argument '{}'",a.positional[1])));}let json_s=if path=="-"{read_stdin()?}else{std::fs::read_to_string(path)?};Ok(Converter::new().execute_instructions(&json_s)?.to_string())}
+        "analyze"=>{let a=Args::parse(rest,&[])?;Ok(converter_at(&a.input()?)?.analysis_report()?)}
+        "to-text"=>{let a=Args::parse(rest,&["alphabet"])?;let alphabet=a.flag("alphabet").unwrap_or(data_interconverter_wasm::core::SIMPLE_TEXT_ALPHABET_ID);Ok(converter_at(&a.input()?)?.text(alphabet)?)}
+        "from-text"=>{let a=Args::parse(rest,&["alphabet"])?;let alphabet=a.flag("alphabet").unwrap_or(data_interconverter_wasm::core::SIMPLE_TEXT_ALPHABET_ID);let mut c=Converter::new();c.set_index_from_text(&a.input()?,alphabet)?;Ok(c.index().to_string())}
+        "to-seq"=>{let a=Args::parse(rest,&["bits","len"])?;let(bits,len)=(a.flag_u32("bits")?,a.flag_u32("len")?);Ok(converter_at(&a.input()?)?.sequence(len,bits)?.iter().map(|v|v.to_string()).collect::<Vec<_>>().join("\n"))}
+        "validate"=>{let a=Args::parse(rest,&[])?;if let Some(p)=a.positional.first(){return Err(CliError::Usage(format!("unexpected argument '{}'",p)));}let(out,passed)=Converter::new().run_validation_suite();if passed{Ok(out)}else{Err(CliError::ValidationFailed(out))}}
+        _=>Err(CliError::Usage(format!("unknown command '{}'",cmd))),
+    }
+}
+
+// A closed downstream pipe (`| head`) is not an error for a filter; other write failures are.
+fn emit(out:&str,ok:ExitCode)->ExitCode{match writeln!(std::io::stdout().lock(),"{}",out){Ok(())=>ok,Err(e) if e.kind()==std::io::ErrorKind::BrokenPipe=>ok,Err(e)=>{eprintln!("error: {}",e);ExitCode::FAILURE}}}
+
+fn main()->ExitCode{
+    let args:Vec<String>=std::env::args().skip(1).collect();
+    let Some(cmd)=args.first() else { eprintln!("{}",USAGE); return ExitCode::from(2); };
+    if cmd=="help"||cmd=="--help"||cmd=="-h" { return emit(USAGE,ExitCode::SUCCESS); }
+    match run(cmd,&args[1..]) {
+        Ok(out)=>emit(&out,ExitCode::SUCCESS),
+        Err(CliError::Usage(m))=>{eprintln!("error: {}\n\n{}",m,USAGE);ExitCode::from(2)}
+        Err(CliError::Convert(e))=>{eprintln!("error [{}]: {}",serde_json::to_value(e.kind).ok().and_then(|v|v.as_str().map(str::to_string)).unwrap_or_default(),e);ExitCode::FAILURE}
+        Err(CliError::Io(e))=>{eprintln!("error: {}",e);ExitCode::FAILURE}
+        // The full suite report still goes to stdout; the exit status tells batch jobs a case failed.
+        Err(CliError::ValidationFailed(out))=>emit(&out,ExitCode::FAILURE),
+    }
+}
@@ -0,0 +1,42 @@
+use data_interconverter_wasm::{Converter, ErrorKind};
+use num_bigint::BigUint;
+
+// Every CI below C(n,k) unranks to a distinct sorted k-subset of 0..n that ranks back to the same CI.
+#[test]
+fn combination_round_trips_for_small_n() {
+    let mut c = Converter::new();
+    for n in 0..=10u32 {
+        for k in 0..=n {
+            let total: u64 = (0..k).fold(1, |r, j| r * u64::from(n - j) / u64::from(j + 1));
+            let mut seen = std::collections::HashSet::new();
+            for i in 0..total {
+                c.set_index(BigUint::from(i));
+                let subset = c.combination(n, k).unwrap();
+                assert_eq!(subset.len(), k as usize);
+                assert!(subset.windows(2).all(|w| w[0] < w[1]) && subset.iter().all(|e| *e < n), "C({n},{k}) #{i}: {subset:?}");
+                assert!(seen.insert(subset.clone()));
+                c.set_index_from_combination(&subset).unwrap();
+                assert_eq!(*c.index(), BigUint::from(i));
+            }
+            c.set_index(BigUint::from(total));
+            assert_eq!(c.combination(n, k).unwrap_err().kind, ErrorKind::IndexTooLarge);
+        }
+    }
+}
+
+#[test]
+fn combination_round_trips_near_the_size_limit() {
+    let mut c = Converter::new();
+    let subset = vec![3, 4_000, 9_999, 16_383];
+    c.set_index_from_combination(&subset).unwrap();
+    assert_eq!(c.combination(1 << 14, 4).unwrap(), subset);
+}
+
+#[test]
+fn combination_rejects_oversized_arguments() {
+    let mut c = Converter::new();
+    assert_eq!(c.combination((1 << 14) + 1, 1).unwrap_err().kind, ErrorKind::InvalidArgument);
+    assert_eq!(c.combination(4_000_000_000, 1).unwrap_err().kind, ErrorKind::InvalidArgument);
+    assert_eq!(c.combination(3, 4).unwrap_err().kind, ErrorKind::InvalidArgument);
+    assert_eq!(c.set_index_from_combination(&[1, 1 << 14]).unwrap_err().kind, ErrorKind::InvalidArgument);
+}